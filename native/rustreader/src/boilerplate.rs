@@ -0,0 +1,191 @@
+//! Document-wide boilerplate stripping: running headers/footers and page-number lines.
+//!
+//! Unlike a fixed list of regexes tuned to one digital edition, this looks at the document as a
+//! whole. It collects the first/last lines of every page, normalizes them so that "Page 12" and
+//! "Page 13" collapse to the same key, and removes any line that recurs across enough pages to be
+//! a running header/footer rather than real content.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Lines present on more than this fraction of pages are treated as a running header/footer.
+pub const DEFAULT_FREQUENCY_THRESHOLD: f64 = 0.6;
+pub const DEFAULT_HEADER_LINES: usize = 2;
+pub const DEFAULT_FOOTER_LINES: usize = 2;
+
+static DIGIT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
+static SPACE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+static BARE_INTEGER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\d{1,4}$").unwrap());
+static LABELED_PAGE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^(p\.?|page)\s*\d{1,4}$").unwrap());
+static ROMAN_NUMERAL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^[ivxlcdm]{1,8}$").unwrap());
+
+/// Strips running headers/footers and standalone page-number lines from `pages`.
+///
+/// Returns the cleaned page texts alongside every distinct line that was stripped, so callers can
+/// audit false positives.
+pub fn strip_boilerplate(
+    pages: &[String],
+    header_lines: usize,
+    footer_lines: usize,
+    frequency_threshold: f64,
+) -> (Vec<String>, Vec<String>) {
+    let page_lines: Vec<Vec<&str>> = pages
+        .iter()
+        .map(|p| p.lines().collect::<Vec<_>>())
+        .collect();
+
+    let running_keys = find_running_lines(&page_lines, header_lines, footer_lines, frequency_threshold);
+
+    let mut stripped = Vec::new();
+    let cleaned_pages = page_lines
+        .iter()
+        .map(|lines| {
+            let footer_start = lines.len().saturating_sub(footer_lines);
+            let kept: Vec<&str> = lines
+                .iter()
+                .enumerate()
+                .filter(|(i, line)| {
+                    let key = normalize_line(line);
+                    // The page-number patterns are ambiguous against real body text (a year, a
+                    // list index, a lone Roman-looking word), so only apply them where a page
+                    // number would actually live: the header/footer window.
+                    let in_header_footer_window = *i < header_lines || *i >= footer_start;
+                    let drop = running_keys.contains(&key)
+                        || (in_header_footer_window && is_page_number_line(line));
+                    if drop {
+                        stripped.push((*line).to_string());
+                    }
+                    !drop
+                })
+                .map(|(_, line)| *line)
+                .collect();
+            kept.join("\n")
+        })
+        .collect();
+
+    stripped.sort();
+    stripped.dedup();
+
+    (cleaned_pages, stripped)
+}
+
+/// Finds normalized line keys that recur across more than `frequency_threshold` of pages among
+/// each page's first `header_lines` and last `footer_lines` lines.
+fn find_running_lines(
+    page_lines: &[Vec<&str>],
+    header_lines: usize,
+    footer_lines: usize,
+    frequency_threshold: f64,
+) -> std::collections::HashSet<String> {
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+
+    for lines in page_lines {
+        let head = lines.iter().take(header_lines);
+        let tail = lines.iter().rev().take(footer_lines);
+        let mut seen_on_page = std::collections::HashSet::new();
+        for line in head.chain(tail) {
+            let key = normalize_line(line);
+            if !key.is_empty() {
+                seen_on_page.insert(key);
+            }
+        }
+        for key in seen_on_page {
+            *counts.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    let page_count = page_lines.len().max(1) as f64;
+    counts
+        .into_iter()
+        .filter(|(_, count)| (*count as f64) / page_count > frequency_threshold)
+        .map(|(key, _)| key)
+        .collect()
+}
+
+/// Collapses digits and whitespace so that e.g. "Page 12" and "Page 13" map to the same key.
+fn normalize_line(line: &str) -> String {
+    let collapsed = DIGIT_RE.replace_all(line.trim(), "#");
+    SPACE_RE.replace_all(&collapsed, " ").trim().to_lowercase()
+}
+
+/// Matches standalone page-number lines: bare integers, "p. N" / "page N", and Roman numerals.
+/// Only meaningful when applied to a line in the header/footer window — see the caller.
+fn is_page_number_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return false;
+    }
+
+    BARE_INTEGER_RE.is_match(trimmed)
+        || LABELED_PAGE_RE.is_match(trimmed)
+        || ROMAN_NUMERAL_RE.is_match(trimmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_line_collapses_digits_and_whitespace() {
+        assert_eq!(normalize_line("Page 12"), normalize_line("Page  13"));
+        assert_eq!(normalize_line("Page 12"), "page #");
+    }
+
+    #[test]
+    fn is_page_number_line_matches_common_forms() {
+        assert!(is_page_number_line("12"));
+        assert!(is_page_number_line("p. 4"));
+        assert!(is_page_number_line("Page 10"));
+        assert!(is_page_number_line("iv"));
+        assert!(!is_page_number_line(""));
+        assert!(!is_page_number_line("Chapter One"));
+    }
+
+    #[test]
+    fn strip_boilerplate_removes_lines_recurring_above_the_frequency_threshold() {
+        let pages = vec![
+            "Running Title\nFirst page body.\n1".to_string(),
+            "Running Title\nSecond page body.\n2".to_string(),
+            "Running Title\nThird page body.\n3".to_string(),
+        ];
+
+        let (cleaned, stripped) = strip_boilerplate(&pages, 1, 1, 0.6);
+
+        assert!(cleaned.iter().all(|page| !page.contains("Running Title")));
+        assert!(cleaned[0].contains("First page body."));
+        assert!(stripped.contains(&"Running Title".to_string()));
+    }
+
+    #[test]
+    fn strip_boilerplate_keeps_lines_below_the_frequency_threshold() {
+        let pages = vec![
+            "Unique Heading A\nBody one.".to_string(),
+            "Unique Heading B\nBody two.".to_string(),
+            "Unique Heading C\nBody three.".to_string(),
+        ];
+
+        let (cleaned, _stripped) = strip_boilerplate(&pages, 1, 1, 0.6);
+
+        assert!(cleaned[0].contains("Unique Heading A"));
+        assert!(cleaned[1].contains("Unique Heading B"));
+    }
+
+    #[test]
+    fn strip_boilerplate_only_applies_page_number_patterns_inside_the_header_footer_window() {
+        // "civil" is made up entirely of Roman-numeral letters (c, i, v, i, l), so it matches the
+        // bare Roman-numeral pattern; it must survive when it shows up in the body, and only get
+        // dropped when it's sitting in the footer window a real page number would occupy. Each
+        // page's first/last line differs, so nothing recurs often enough to be a running line.
+        let pages = vec![
+            "Heading A\ncivil\nBody one.\nFooter A".to_string(),
+            "Heading B\nBody two.\ncivil".to_string(),
+            "Heading C\nBody three.\nFooter C".to_string(),
+        ];
+
+        let (cleaned, stripped) = strip_boilerplate(&pages, 1, 1, 0.6);
+
+        assert!(cleaned[0].lines().any(|line| line == "civil"));
+        assert!(!cleaned[1].lines().any(|line| line == "civil"));
+        assert!(stripped.contains(&"civil".to_string()));
+    }
+}
@@ -0,0 +1,120 @@
+//! Assembles an EPUB from already-segmented, boilerplate-stripped page text, reusing the same
+//! pipeline as [`crate::extract_pdf`] rather than re-parsing the source PDF.
+
+use crate::metadata;
+use epub_builder::{EpubBuilder, EpubContent, ReferenceType, ZipLibrary};
+use std::io::Cursor;
+
+pub struct EpubMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+}
+
+/// Result of assembling the EPUB: how many non-empty pages became chapters, and how many of the
+/// pages handed to [`write_epub`] turned out to be empty after all. In practice `pages` has
+/// already been through [`crate::segment_pages`]'s length filter by the time it gets here, so this
+/// is usually 0 — callers that want the real skip count should add `Segmentation::skipped_pages`
+/// on top of this one.
+pub struct EpubResult {
+    pub chapters_written: usize,
+    pub skipped_pages: usize,
+}
+
+/// Parses an override metadata string (a small `{"title": ..., "author": ...}` JSON object, as
+/// supplied by the caller) and falls back to fields pulled out of the PDF's own metadata string
+/// when a field is missing or the override isn't valid JSON.
+pub fn resolve_metadata(override_json: &str, detected_metadata: &str) -> EpubMetadata {
+    let overrides: serde_json::Value =
+        serde_json::from_str(override_json).unwrap_or(serde_json::Value::Null);
+
+    let title = overrides
+        .get("title")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| metadata::extract_field(detected_metadata, &["dc:title", "title"]));
+
+    let author = overrides
+        .get("author")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| metadata::extract_field(detected_metadata, &["dc:creator", "Author", "author"]));
+
+    EpubMetadata { title, author }
+}
+
+/// Builds an EPUB with one XHTML chapter per non-empty page and writes it to `out_path`.
+pub fn write_epub(
+    pages: &[(usize, String)],
+    metadata: &EpubMetadata,
+    out_path: &str,
+) -> Result<EpubResult, String> {
+    let mut builder =
+        EpubBuilder::new(ZipLibrary::new().map_err(|e| format!("Failed to init EPUB zip: {}", e))?)
+            .map_err(|e| format!("Failed to init EPUB builder: {}", e))?;
+
+    if let Some(title) = &metadata.title {
+        builder.metadata("title", title).map_err(|e| e.to_string())?;
+    }
+    if let Some(author) = &metadata.author {
+        builder.metadata("author", author).map_err(|e| e.to_string())?;
+    }
+
+    let mut chapters_written = 0;
+    let mut skipped_pages = 0;
+
+    for (page_number, text) in pages {
+        if text.trim().is_empty() {
+            skipped_pages += 1;
+            continue;
+        }
+
+        let chapter_file = format!("page_{}.xhtml", page_number);
+        let xhtml = page_to_xhtml(*page_number, text);
+
+        builder
+            .add_content(
+                EpubContent::new(chapter_file, xhtml.as_bytes())
+                    .title(format!("Page {}", page_number))
+                    .reftype(ReferenceType::Text),
+            )
+            .map_err(|e| format!("Failed to add chapter for page {}: {}", page_number, e))?;
+
+        chapters_written += 1;
+    }
+
+    let mut out_file = std::fs::File::create(out_path)
+        .map_err(|e| format!("Failed to create {}: {}", out_path, e))?;
+    builder
+        .generate(&mut out_file)
+        .map_err(|e| format!("Failed to write EPUB: {}", e))?;
+
+    Ok(EpubResult {
+        chapters_written,
+        skipped_pages,
+    })
+}
+
+fn page_to_xhtml(page_number: usize, text: &str) -> String {
+    let escaped = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    let paragraphs: String = escaped
+        .split("\n\n")
+        .map(|p| format!("<p>{}</p>", p.replace('\n', "<br/>")))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut xhtml = Cursor::new(Vec::new());
+    use std::io::Write;
+    let _ = write!(
+        xhtml,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE html>\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\">\n\
+         <head><title>Page {page_number}</title></head>\n\
+         <body>\n{paragraphs}\n</body>\n</html>"
+    );
+
+    String::from_utf8(xhtml.into_inner()).unwrap_or_default()
+}
@@ -0,0 +1,107 @@
+//! Per-page language identification and stopword filtering, layered on top of the existing
+//! segmentation pipeline so search/NLP callers can branch on language without re-parsing.
+
+use lingua::{Language, LanguageDetector, LanguageDetectorBuilder};
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+
+/// Result of running the language detector over a single page's text.
+pub struct LanguageTag {
+    /// ISO 639-1 code, e.g. "en" or "zh".
+    pub code: String,
+    pub confidence: f64,
+}
+
+/// Built once per process, over every language lingua supports: restricting the candidate set to
+/// just English/Chinese (the languages we carry stopword lists for) would make those two always
+/// win against each other, silently mislabeling e.g. French or German pages. The restriction only
+/// matters once we get to [`stopwords_for`]/[`language_from_code`].
+static DETECTOR: Lazy<LanguageDetector> =
+    Lazy::new(|| LanguageDetectorBuilder::from_all_languages().build());
+
+pub fn build_detector() -> &'static LanguageDetector {
+    &DETECTOR
+}
+
+/// Detects the dominant language of `text`, returning its ISO 639-1 code and the confidence
+/// value the detector assigns to that language.
+pub fn detect(detector: &LanguageDetector, text: &str) -> Option<LanguageTag> {
+    // compute_language_confidence_values already returns languages sorted by descending
+    // confidence, so the first entry is the same winner detect_language_of would pick.
+    let (language, confidence) = detector
+        .compute_language_confidence_values(text)
+        .into_iter()
+        .next()?;
+
+    Some(LanguageTag {
+        code: language.iso_code_639_1().to_string().to_lowercase(),
+        confidence,
+    })
+}
+
+/// Maps an ISO 639-1 code back to a [`Language`] for the languages we carry a stopword list for.
+pub fn language_from_code(code: &str) -> Option<Language> {
+    match code {
+        "en" => Some(Language::English),
+        "zh" => Some(Language::Chinese),
+        _ => None,
+    }
+}
+
+/// Removes stopwords for `language`, falling back to returning `text` unchanged for languages we
+/// don't carry a stopword list for. Words are split on whitespace only and rejoined with single
+/// spaces, so in text written without spaces between words (e.g. Chinese) a stopword is removed
+/// only where it stands as its own whitespace-separated token.
+pub fn remove_stopwords(text: &str, language: Language) -> String {
+    let stopwords = stopwords_for(language);
+    if stopwords.is_empty() {
+        return text.to_string();
+    }
+
+    text.split_whitespace()
+        .filter(|word| !stopwords.contains(&word.to_lowercase().trim_matches(|c: char| !c.is_alphanumeric()).to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn stopwords_for(language: Language) -> HashSet<String> {
+    let words: &[&str] = match language {
+        Language::English => &[
+            "the", "a", "an", "and", "or", "but", "of", "to", "in", "on", "for", "is", "are",
+            "was", "were", "it", "this", "that", "as", "with", "at", "by", "be", "been",
+        ],
+        Language::Chinese => &["的", "了", "和", "是", "在", "我", "有", "他", "这", "那"],
+        _ => &[],
+    };
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_stopwords_drops_english_stopwords_case_insensitively() {
+        let result = remove_stopwords("The cat sat ON the mat", Language::English);
+        assert_eq!(result, "cat sat mat");
+    }
+
+    #[test]
+    fn remove_stopwords_strips_surrounding_punctuation_before_matching() {
+        let result = remove_stopwords("(The) cat, (is) sleeping.", Language::English);
+        assert_eq!(result, "cat, sleeping.");
+    }
+
+    #[test]
+    fn remove_stopwords_leaves_text_unchanged_for_unsupported_language() {
+        let result = remove_stopwords("le chat est noir", Language::French);
+        assert_eq!(result, "le chat est noir");
+    }
+
+    #[test]
+    fn language_from_code_only_maps_supported_codes() {
+        assert_eq!(language_from_code("en"), Some(Language::English));
+        assert_eq!(language_from_code("zh"), Some(Language::Chinese));
+        assert_eq!(language_from_code("fr"), None);
+    }
+}
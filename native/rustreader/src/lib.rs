@@ -1,96 +1,247 @@
-use extractous::Extractor;
-use regex::Regex;
 use rustler::NifResult;
 use serde::Serialize;
 
+mod boilerplate;
+mod epub;
+mod language;
+mod markdown;
+mod metadata;
+mod stext;
+
 #[derive(Serialize)]
 struct Page {
     page_number: usize,
     text_content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_confidence: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_stopworded: Option<String>,
+}
+
+/// Raw, boilerplate-stripped page text plus the document metadata and the lines that were
+/// stripped, shared by every NIF built on top of the segmentation + cleaning pipeline.
+struct Segmentation {
+    pages: Vec<(usize, String)>,
+    metadata: String,
+    stripped_lines: Vec<String>,
+    /// Pages dropped by the post-cleaning empty/too-short filter (currently `< 50` bytes), so
+    /// callers that care about skipped pages (e.g. EPUB export) don't have to re-derive it from a
+    /// check that, by the time they see the page text, has already run.
+    skipped_pages: usize,
 }
 
+fn segment_pages(
+    path: &str,
+    header_lines: usize,
+    footer_lines: usize,
+    frequency_threshold: f64,
+) -> Result<Segmentation, String> {
+    // One mupdf parse gives us both the real per-page boundaries the boilerplate detector needs
+    // and the document's own metadata, instead of parsing the PDF twice (once here, once via
+    // extractous just to read the metadata).
+    let (raw_pages, metadata) = stext::extract_plain_pages(path)
+        .map_err(|e| format!("Page extraction failed: {}", e))?;
+    let pages: Vec<String> = raw_pages.into_iter().map(|text| text.trim().to_string()).collect();
+
+    // Strip running headers/footers and standalone page-number lines across the whole
+    // document before the empty-page filter runs.
+    let (cleaned_pages, stripped_lines) =
+        boilerplate::strip_boilerplate(&pages, header_lines, footer_lines, frequency_threshold);
+
+    let total_pages = cleaned_pages.len();
+    let filtered_pages: Vec<(usize, String)> = cleaned_pages
+        .into_iter()
+        .enumerate()
+        .filter(|(_, text)| text.len() >= 50)
+        .map(|(i, text)| (i + 1, text))
+        .collect();
+    let skipped_pages = total_pages - filtered_pages.len();
+
+    Ok(Segmentation {
+        pages: filtered_pages,
+        metadata,
+        stripped_lines,
+        skipped_pages,
+    })
+}
+
+/// Keeps its original `(Vec<String>, String)` arity stable for existing callers. Use
+/// [`extract_pdf_tunable`] for a tunable boilerplate detector window/threshold and visibility into
+/// which lines got stripped.
 #[rustler::nif(schedule = "DirtyCpu")]
 fn extract_pdf(path: String) -> NifResult<(Vec<String>, String)> {
-    let extractor = Extractor::new();
-
-    match extractor.extract_file_to_string(&path) {
-        Ok((raw_content, metadata)) => {
-            // Clean content of common PDF screen controls and artifacts
-            let content = clean_pdf_controls(&raw_content);
-
-            let chunk_size = 1500;
-            let mut pages = Vec::new();
-
-            // Process entire content starting from page 1
-            let chars: Vec<char> = content.chars().collect();
-            let chunks = chars.chunks(chunk_size);
-            
-            for (i, chunk) in chunks.enumerate() {
-                let text: String = chunk.iter().collect();
-                let page_json = serde_json::json!({
-                    "page_number": i + 1,
-                    "text_content": text.trim()
-                });
-                pages.push(page_json.to_string());
-            }
-            
-            // Filter out empty pages
-            let filtered_pages: Vec<String> = pages
+    let (pages_json, metadata, _stripped_lines) = extract_pdf_tunable(
+        path,
+        boilerplate::DEFAULT_HEADER_LINES,
+        boilerplate::DEFAULT_FOOTER_LINES,
+        boilerplate::DEFAULT_FREQUENCY_THRESHOLD,
+    )?;
+    Ok((pages_json, metadata))
+}
+
+/// Same as [`extract_pdf`], but with the boilerplate detector's window and threshold exposed so
+/// callers can tune it for documents with unusually long/short running headers or footers, and
+/// with the stripped lines surfaced for auditing.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn extract_pdf_tunable(
+    path: String,
+    header_lines: usize,
+    footer_lines: usize,
+    frequency_threshold: f64,
+) -> NifResult<(Vec<String>, String, Vec<String>)> {
+    match segment_pages(&path, header_lines, footer_lines, frequency_threshold) {
+        Ok(segmentation) => {
+            let pages_json: Vec<String> = segmentation
+                .pages
+                .into_iter()
+                .map(|(page_number, text_content)| {
+                    serde_json::json!({
+                        "page_number": page_number,
+                        "text_content": text_content
+                    })
+                    .to_string()
+                })
+                .collect();
+
+            Ok((pages_json, segmentation.metadata, segmentation.stripped_lines))
+        }
+        Err(e) => Err(rustler::Error::Term(Box::new(e))),
+    }
+}
+
+/// Same segmentation and cleaning pipeline as [`extract_pdf`], but each page is additionally
+/// tagged with its detected language (ISO 639-1 code) and a confidence score. When
+/// `include_stopworded` is true, each page also carries a `content_stopworded` variant with
+/// language-appropriate stopwords removed, so indexing code can choose between raw and filtered
+/// text.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn extract_pdf_with_language(path: String, include_stopworded: bool) -> NifResult<(Vec<String>, String)> {
+    match segment_pages(
+        &path,
+        boilerplate::DEFAULT_HEADER_LINES,
+        boilerplate::DEFAULT_FOOTER_LINES,
+        boilerplate::DEFAULT_FREQUENCY_THRESHOLD,
+    ) {
+        Ok(segmentation) => {
+            let detector = language::build_detector();
+
+            let pages_json: Vec<String> = segmentation
+                .pages
                 .into_iter()
-                .filter(|p| {
-                    if let Ok(json) = serde_json::from_str::<serde_json::Value>(p) {
-                        if let Some(text) = json["text_content"].as_str() {
-                            return text.len() >= 50;
-                        }
-                    }
-                    false
+                .map(|(page_number, text_content)| {
+                    let tag = language::detect(detector, &text_content);
+                    let content_stopworded = if include_stopworded {
+                        tag.as_ref()
+                            .and_then(|t| language::language_from_code(&t.code))
+                            .map(|lang| language::remove_stopwords(&text_content, lang))
+                    } else {
+                        None
+                    };
+
+                    let page = Page {
+                        page_number,
+                        text_content,
+                        language: tag.as_ref().map(|t| t.code.clone()),
+                        language_confidence: tag.as_ref().map(|t| t.confidence),
+                        content_stopworded,
+                    };
+                    serde_json::to_string(&page).unwrap_or_default()
                 })
                 .collect();
 
-            Ok((filtered_pages, format!("{:?}", metadata)))
-        },
-        Err(e) => Err(rustler::Error::Term(Box::new(format!("Extraction failed: {}", e))))
+            Ok((pages_json, segmentation.metadata))
+        }
+        Err(e) => Err(rustler::Error::Term(Box::new(e))),
     }
 }
 
-fn clean_pdf_controls(text: &str) -> String {
-    // Remove standard PDF screen controls and UI artifacts
-    // These appear in interactive digital editions (like BookVirtual)
-    let patterns = [
-        r"Fit Page Full Scre[e]?", // Matches Scre or Scree
-        r"Navigate Contr",
-        r"[n/]*Off Close Book",
-        r"[ol]+ Internet",
-        r"en O",
-        r"n O",
-        r"Digital Interface by.*",
-        r"U\.S\. Patent Pending.*",
-        r"© 2000 All Rights Reserved\.",
-        r"BookVirtual™",
-        r"www\.bookvirtual\.com",
-        r"DOWN THE\s*\d+",       // Handle "DOWN THE4" or "DOWN THE 4"
-        r"RABBIT-HOLE\. \d+",
-        r"B \d+"
-    ];
-
-    let mut cleaned = text.to_string();
-    for pattern in patterns.iter() {
-        if let Ok(re) = Regex::new(pattern) {
-            // Replace with a space to prevent merging words if the artifact 
-            // was inserted in the middle of a sentence (e.g. "listen to [ARTIFACT] her")
-            cleaned = re.replace_all(&cleaned, " ").to_string();
+/// Page-accurate alternative to [`extract_pdf`]: loads each real PDF page through mupdf and
+/// serializes its structured text tree (blocks -> lines -> spans/chars), each span carrying a
+/// `bbox`, font name and size, so callers can do coordinate-aware highlighting, column
+/// detection, and reflow instead of guessing from character counts.
+///
+/// `page_number` matches the document's real page index (1-based), and each page JSON includes
+/// its `width`/`height` so clients can normalize coordinates.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn extract_pdf_structured(path: String) -> NifResult<(Vec<String>, String)> {
+    match stext::extract_structured_pages(&path) {
+        Ok((pages, metadata)) => {
+            let serialized: Result<Vec<String>, _> =
+                pages.iter().map(serde_json::to_string).collect();
+            match serialized {
+                Ok(pages_json) => Ok((pages_json, metadata)),
+                Err(e) => Err(rustler::Error::Term(Box::new(format!(
+                    "Failed to serialize structured page: {}",
+                    e
+                )))),
+            }
         }
+        Err(e) => Err(rustler::Error::Term(Box::new(format!(
+            "Structured extraction failed: {}",
+            e
+        )))),
     }
-    
-    // Collapse multiple spaces
-    let space_re = Regex::new(r" +").unwrap();
-    cleaned = space_re.replace_all(&cleaned, " ").to_string();
+}
 
-    // Collapse multiple newlines
-    let newline_re = Regex::new(r"\n{3,}").unwrap();
-    cleaned = newline_re.replace_all(&cleaned, "\n\n").to_string();
+/// Re-extracts and cleans `path` the same way [`extract_pdf`] does, then assembles the result
+/// into an EPUB at `out_path`: one XHTML chapter per real page, with title/author pulled from
+/// `metadata` (a `{"title": ..., "author": ...}` override) or, failing that, the PDF's own
+/// metadata.
+///
+/// Returns `(chapters_written, skipped_pages)`. `skipped_pages` counts every page
+/// [`segment_pages`] dropped as too short to be real content, not just the (rarely reachable)
+/// empty-after-cleaning pages [`epub::write_epub`] itself guards against.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn pdf_to_epub(path: String, out_path: String, metadata: String) -> NifResult<(usize, usize)> {
+    match segment_pages(
+        &path,
+        boilerplate::DEFAULT_HEADER_LINES,
+        boilerplate::DEFAULT_FOOTER_LINES,
+        boilerplate::DEFAULT_FREQUENCY_THRESHOLD,
+    ) {
+        Ok(segmentation) => {
+            let epub_metadata = epub::resolve_metadata(&metadata, &segmentation.metadata);
+            match epub::write_epub(&segmentation.pages, &epub_metadata, &out_path) {
+                Ok(result) => Ok((
+                    result.chapters_written,
+                    segmentation.skipped_pages + result.skipped_pages,
+                )),
+                Err(e) => Err(rustler::Error::Term(Box::new(e))),
+            }
+        }
+        Err(e) => Err(rustler::Error::Term(Box::new(e))),
+    }
+}
 
-    cleaned
+/// Same segmentation and cleaning pipeline as [`extract_pdf`], but flattened into a single
+/// Markdown string instead of a JSON-per-page vector. When `include_front_matter` is set, a
+/// `---`-fenced YAML front-matter block carrying title/author/creation date (pulled from the PDF
+/// metadata) and the number of pages rendered after the short-page filter is prepended; pages
+/// are then joined with `page_separator`.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn extract_pdf_to_markdown(
+    path: String,
+    include_front_matter: bool,
+    page_separator: String,
+) -> NifResult<String> {
+    match segment_pages(
+        &path,
+        boilerplate::DEFAULT_HEADER_LINES,
+        boilerplate::DEFAULT_FOOTER_LINES,
+        boilerplate::DEFAULT_FREQUENCY_THRESHOLD,
+    ) {
+        Ok(segmentation) => {
+            let options = markdown::MarkdownOptions {
+                include_front_matter,
+                page_separator,
+            };
+            Ok(markdown::render(&segmentation.pages, &segmentation.metadata, &options))
+        }
+        Err(e) => Err(rustler::Error::Term(Box::new(e))),
+    }
 }
 
 rustler::init!("Elixir.RustReader");
+
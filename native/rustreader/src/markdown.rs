@@ -0,0 +1,135 @@
+//! Flattens already-segmented, boilerplate-stripped pages into a single Markdown document, with
+//! an optional YAML front-matter block carrying the PDF's metadata.
+
+use crate::metadata;
+
+pub struct MarkdownOptions {
+    pub include_front_matter: bool,
+    pub page_separator: String,
+}
+
+/// Renders `pages` as Markdown, analogous to how rustdoc's `extract_leading_metadata` peels
+/// `%`-prefixed lines off the top of a file: here we prepend a `---`-fenced front-matter block
+/// instead, then join page bodies with `options.page_separator`.
+pub fn render(pages: &[(usize, String)], detected_metadata: &str, options: &MarkdownOptions) -> String {
+    let mut out = String::new();
+
+    if options.include_front_matter {
+        out.push_str(&front_matter(pages.len(), detected_metadata));
+    }
+
+    let body = pages
+        .iter()
+        .map(|(_, text)| render_page(text))
+        .collect::<Vec<_>>()
+        .join(&options.page_separator);
+
+    out.push_str(&body);
+    out
+}
+
+fn front_matter(page_count: usize, detected_metadata: &str) -> String {
+    let title = metadata::extract_field(detected_metadata, &["dc:title", "title"]);
+    let author = metadata::extract_field(detected_metadata, &["dc:creator", "Author", "author"]);
+    let created = metadata::extract_field(
+        detected_metadata,
+        &["dcterms:created", "Creation-Date", "created"],
+    );
+
+    let mut lines = vec!["---".to_string()];
+    if let Some(title) = title {
+        lines.push(format!("title: \"{}\"", yaml_escape(&title)));
+    }
+    if let Some(author) = author {
+        lines.push(format!("author: \"{}\"", yaml_escape(&author)));
+    }
+    lines.push(format!("page_count: {}", page_count));
+    if let Some(created) = created {
+        lines.push(format!("created: \"{}\"", yaml_escape(&created)));
+    }
+    lines.push("---\n\n".to_string());
+
+    lines.join("\n")
+}
+
+/// Escapes a value for a double-quoted YAML scalar: backslashes and quotes need escaping, and
+/// newlines can't appear literally inside a double-quoted flow scalar.
+fn yaml_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Promotes short, all-caps lines (likely headings in the source PDF) to Markdown headings,
+/// preserving their original casing so acronyms (e.g. "NASA") aren't mangled: a short line
+/// becomes `##`, a longer one becomes `#`.
+fn render_page(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let trimmed = line.trim();
+            if is_heading_candidate(trimmed) {
+                if trimmed.split_whitespace().count() <= 3 {
+                    format!("## {}", trimmed)
+                } else {
+                    format!("# {}", trimmed)
+                }
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Only an "obvious" all-caps short line qualifies: at least two alphabetic characters (so a
+/// lone "I" doesn't count), no more than 8 words, and no more than 40 characters.
+fn is_heading_candidate(line: &str) -> bool {
+    let alphabetic: String = line.chars().filter(|c| c.is_alphabetic()).collect();
+    alphabetic.chars().count() >= 2
+        && alphabetic.chars().all(|c| c.is_uppercase())
+        && line.chars().count() <= 40
+        && line.split_whitespace().count() <= 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_heading_candidate_accepts_a_short_all_caps_line() {
+        assert!(is_heading_candidate("CHAPTER ONE"));
+    }
+
+    #[test]
+    fn is_heading_candidate_rejects_a_lone_capital_letter() {
+        assert!(!is_heading_candidate("I"));
+    }
+
+    #[test]
+    fn is_heading_candidate_rejects_mixed_case() {
+        assert!(!is_heading_candidate("Chapter One"));
+    }
+
+    #[test]
+    fn is_heading_candidate_rejects_lines_over_the_length_and_word_limits() {
+        assert!(!is_heading_candidate(
+            "THIS ALL CAPS LINE IS WAY TOO LONG TO BE A HEADING"
+        ));
+        assert!(!is_heading_candidate("ONE TWO THREE FOUR FIVE SIX SEVEN EIGHT NINE"));
+    }
+
+    #[test]
+    fn render_page_promotes_short_heading_to_h2_and_longer_one_to_h1() {
+        let rendered = render_page("HI\nNASA LAUNCHES NEW SATELLITE TODAY\nRegular body text.");
+        assert_eq!(
+            rendered,
+            "## HI\n# NASA LAUNCHES NEW SATELLITE TODAY\nRegular body text."
+        );
+    }
+
+    #[test]
+    fn yaml_escape_escapes_backslashes_quotes_and_newlines() {
+        assert_eq!(yaml_escape("a \"quoted\"\\line\nbreak"), "a \\\"quoted\\\"\\\\line\\nbreak");
+    }
+}
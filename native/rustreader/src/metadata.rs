@@ -0,0 +1,58 @@
+//! Helpers for pulling structured fields out of the PDF metadata string built by
+//! `stext::document_metadata`, shared by every export format that wants title/author/date
+//! without re-parsing the source PDF.
+
+use regex::Regex;
+
+/// The metadata string is a JSON-like map whose values are either plain strings or string arrays
+/// (e.g. `{"title": "Alice"}` or `{"dc:title": ["Alice"], ...}`); pull the first value out of
+/// whichever of `keys` shows up first.
+pub fn extract_field(detected_metadata: &str, keys: &[&str]) -> Option<String> {
+    for key in keys {
+        let pattern = format!(r#""{}"\s*:\s*\[?\s*"([^"]+)""#, regex::escape(key));
+        if let Ok(re) = Regex::new(&pattern) {
+            if let Some(caps) = re.captures(detected_metadata) {
+                return caps.get(1).map(|m| m.as_str().to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_field_reads_a_plain_string_value() {
+        let metadata = r#"{"dc:title": "Alice in Wonderland"}"#;
+        assert_eq!(
+            extract_field(metadata, &["dc:title", "title"]),
+            Some("Alice in Wonderland".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_field_reads_a_value_wrapped_in_an_array() {
+        let metadata = r#"{"dc:title": ["Alice in Wonderland"]}"#;
+        assert_eq!(
+            extract_field(metadata, &["dc:title", "title"]),
+            Some("Alice in Wonderland".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_field_falls_back_through_keys_in_order() {
+        let metadata = r#"{"title": "Alice in Wonderland"}"#;
+        assert_eq!(
+            extract_field(metadata, &["dc:title", "title"]),
+            Some("Alice in Wonderland".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_field_returns_none_when_no_key_matches() {
+        let metadata = r#"{"page_count": 10}"#;
+        assert_eq!(extract_field(metadata, &["dc:title", "title"]), None);
+    }
+}
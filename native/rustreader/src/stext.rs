@@ -0,0 +1,151 @@
+//! Structured text extraction backed by mupdf's page tree (blocks -> lines -> spans/chars),
+//! mirroring the shape `mutool convert -o out.stext.json` / `stext_page_as_json` would emit.
+
+use mupdf::{Document, TextPageOptions};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct StructuredPage {
+    pub page_number: usize,
+    pub width: f32,
+    pub height: f32,
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Serialize)]
+pub struct Block {
+    pub bbox: [f32; 4],
+    pub lines: Vec<Line>,
+}
+
+#[derive(Serialize)]
+pub struct Line {
+    pub bbox: [f32; 4],
+    pub spans: Vec<Span>,
+}
+
+#[derive(Serialize)]
+pub struct Span {
+    pub bbox: [f32; 4],
+    pub font: String,
+    pub size: f32,
+    pub text: String,
+    pub chars: Vec<Char>,
+}
+
+#[derive(Serialize)]
+pub struct Char {
+    pub bbox: [f32; 4],
+    pub c: char,
+}
+
+/// Loads every real page of `path` and builds its structured text tree, one [`StructuredPage`]
+/// per page, in document order, alongside the document's own metadata (title/author/dates),
+/// pulled from the same parse.
+pub fn extract_structured_pages(path: &str) -> Result<(Vec<StructuredPage>, String), mupdf::Error> {
+    let document = Document::open(path)?;
+    let metadata = document_metadata(&document);
+    let mut pages = Vec::new();
+
+    for (index, page) in document.pages()?.enumerate() {
+        let page = page?;
+        let bounds = page.bounds()?;
+        let text_page = page.to_text_page(TextPageOptions::empty())?;
+
+        let mut blocks = Vec::new();
+        for block in text_page.blocks() {
+            let mut lines = Vec::new();
+            for line in block.lines() {
+                let mut spans = Vec::new();
+                for span in line.chars().as_slice().chunk_by(|a, b| a.font == b.font && a.size == b.size) {
+                    let chars: Vec<Char> = span
+                        .iter()
+                        .map(|c| Char {
+                            bbox: [c.bbox.x0, c.bbox.y0, c.bbox.x1, c.bbox.y1],
+                            c: c.char,
+                        })
+                        .collect();
+                    let text: String = chars.iter().map(|c| c.c).collect();
+                    let (font, size) = span
+                        .first()
+                        .map(|c| (c.font.clone(), c.size))
+                        .unwrap_or_default();
+                    let bbox = span_bbox(span);
+                    spans.push(Span { bbox, font, size, text, chars });
+                }
+                lines.push(Line { bbox: rect_to_array(line.bounds()), spans });
+            }
+            blocks.push(Block { bbox: rect_to_array(block.bounds()), lines });
+        }
+
+        pages.push(StructuredPage {
+            page_number: index + 1,
+            width: bounds.width(),
+            height: bounds.height(),
+            blocks,
+        });
+    }
+
+    Ok((pages, metadata))
+}
+
+/// Loads every real page of `path` and returns its plain text (one string per page, in document
+/// order) alongside the document's metadata, so the segmentation pipeline can work against real
+/// page boundaries and get title/author/dates from a single parse instead of opening the PDF
+/// twice (once here, once in extractous).
+pub fn extract_plain_pages(path: &str) -> Result<(Vec<String>, String), mupdf::Error> {
+    let document = Document::open(path)?;
+    let metadata = document_metadata(&document);
+    let mut pages = Vec::new();
+
+    for page in document.pages()? {
+        let page = page?;
+        let text_page = page.to_text_page(TextPageOptions::empty())?;
+
+        let mut lines = Vec::new();
+        for block in text_page.blocks() {
+            for line in block.lines() {
+                lines.push(line.chars().map(|c| c.char).collect::<String>());
+            }
+        }
+        pages.push(lines.join("\n"));
+    }
+
+    Ok((pages, metadata))
+}
+
+/// Reads the PDF's info dictionary (title/author/creation date) into the same
+/// `"key": "value"` shape [`crate::metadata::extract_field`] already knows how to parse, so
+/// callers that used to read extractous' metadata debug string keep working unchanged.
+fn document_metadata(document: &Document) -> String {
+    let fields = [
+        ("title", "info:Title"),
+        ("Author", "info:Author"),
+        ("Creation-Date", "info:CreationDate"),
+    ];
+
+    let parts: Vec<String> = fields
+        .iter()
+        .filter_map(|(json_key, mupdf_key)| {
+            let value = document.metadata(mupdf_key).ok()?;
+            if value.is_empty() {
+                return None;
+            }
+            Some(format!("\"{}\": \"{}\"", json_key, value.replace('"', "\\\"")))
+        })
+        .collect();
+
+    format!("{{{}}}", parts.join(", "))
+}
+
+fn rect_to_array(rect: mupdf::Rect) -> [f32; 4] {
+    [rect.x0, rect.y0, rect.x1, rect.y1]
+}
+
+fn span_bbox(chars: &[mupdf::TextChar]) -> [f32; 4] {
+    let x0 = chars.iter().map(|c| c.bbox.x0).fold(f32::INFINITY, f32::min);
+    let y0 = chars.iter().map(|c| c.bbox.y0).fold(f32::INFINITY, f32::min);
+    let x1 = chars.iter().map(|c| c.bbox.x1).fold(f32::NEG_INFINITY, f32::max);
+    let y1 = chars.iter().map(|c| c.bbox.y1).fold(f32::NEG_INFINITY, f32::max);
+    [x0, y0, x1, y1]
+}